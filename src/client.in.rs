@@ -0,0 +1,204 @@
+use std::io::Read;
+
+use hyper::Client as HttpClient;
+use hyper::header::{Accept, Authorization, ContentType, Headers, qitem};
+use hyper::method::Method;
+use hyper::status::StatusCode;
+use serde::de::DeserializeOwned;
+use serde_json;
+
+use super::types::contact_methods::{ContactMethod, ContactMethods};
+
+const BASE_URL: &str = "https://api.pagerduty.com";
+const ACCEPT_VERSION: &str = "application/vnd.pagerduty+json;version=2";
+
+
+#[derive(Debug)]
+pub enum Error {
+    Http(::hyper::Error),
+    Io(::std::io::Error),
+    Json(serde_json::Error),
+    Api(StatusCode, String),
+}
+
+impl From<::hyper::Error> for Error {
+    fn from(err: ::hyper::Error) -> Error {
+        Error::Http(err)
+    }
+}
+
+impl From<::std::io::Error> for Error {
+    fn from(err: ::std::io::Error) -> Error {
+        Error::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Error {
+        Error::Json(err)
+    }
+}
+
+pub type Result<T> = ::std::result::Result<T, Error>;
+
+
+#[derive(Serialize, Deserialize)]
+struct ContactMethodEnvelope {
+    contact_method: ContactMethod,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ContactMethodsEnvelope {
+    contact_methods: ContactMethods,
+}
+
+
+/// A minimal client for the `contact_methods` portion of the
+/// PagerDuty REST API (https://api.pagerduty.com).
+pub struct Client {
+    token: String,
+    base_url: String,
+    http: HttpClient,
+}
+
+impl Client {
+    pub fn new(token: &str) -> Client {
+        Client::with_base_url(token, BASE_URL)
+    }
+
+    /// Like `new`, but talks to `base_url` instead of the production
+    /// PagerDuty API. Useful for pointing the client at a test server.
+    pub fn with_base_url(token: &str, base_url: &str) -> Client {
+        Client {
+            token: token.to_owned(),
+            base_url: base_url.to_owned(),
+            http: HttpClient::new(),
+        }
+    }
+
+    fn headers(&self) -> Headers {
+        let mut headers = Headers::new();
+        headers.set(Authorization(format!("Token token={}", self.token)));
+        headers.set(Accept(vec![qitem(ACCEPT_VERSION.parse().unwrap())]));
+        headers
+    }
+
+    fn send(&self, method: Method, path: &str, body: Option<String>) -> Result<String> {
+        let url = format!("{}{}", self.base_url, path);
+        let mut headers = self.headers();
+        if body.is_some() {
+            headers.set(ContentType::json());
+        }
+
+        let mut request = self.http.request(method, &url).headers(headers);
+        if let Some(ref body) = body {
+            request = request.body(body.as_str());
+        }
+
+        let mut response = request.send()?;
+        let mut data = String::new();
+        response.read_to_string(&mut data)?;
+
+        if !response.status.is_success() {
+            return Err(Error::Api(response.status, data));
+        }
+
+        Ok(data)
+    }
+
+    fn request<T>(&self, method: Method, path: &str, body: Option<String>) -> Result<T>
+        where T: DeserializeOwned
+    {
+        let data = self.send(method, path, body)?;
+        Ok(serde_json::from_str(&data)?)
+    }
+
+    pub fn list_contact_methods(&self, user_id: &str) -> Result<ContactMethods> {
+        let path = format!("/users/{}/contact_methods", user_id);
+        let envelope: ContactMethodsEnvelope = self.request(Method::Get, &path, None)?;
+        Ok(envelope.contact_methods)
+    }
+
+    pub fn get_contact_method(&self, user_id: &str, id: &str) -> Result<ContactMethod> {
+        let path = format!("/users/{}/contact_methods/{}", user_id, id);
+        let envelope: ContactMethodEnvelope = self.request(Method::Get, &path, None)?;
+        Ok(envelope.contact_method)
+    }
+
+    pub fn create_contact_method(&self, user_id: &str, contact_method: &ContactMethod) -> Result<ContactMethod> {
+        let path = format!("/users/{}/contact_methods", user_id);
+        let body = serde_json::to_string(&ContactMethodEnvelopeRef { contact_method })?;
+        let envelope: ContactMethodEnvelope = self.request(Method::Post, &path, Some(body))?;
+        Ok(envelope.contact_method)
+    }
+
+    pub fn update_contact_method(&self, user_id: &str, id: &str, contact_method: &ContactMethod) -> Result<ContactMethod> {
+        let path = format!("/users/{}/contact_methods/{}", user_id, id);
+        let body = serde_json::to_string(&ContactMethodEnvelopeRef { contact_method })?;
+        let envelope: ContactMethodEnvelope = self.request(Method::Put, &path, Some(body))?;
+        Ok(envelope.contact_method)
+    }
+
+    pub fn delete_contact_method(&self, user_id: &str, id: &str) -> Result<()> {
+        let path = format!("/users/{}/contact_methods/{}", user_id, id);
+        self.send(Method::Delete, &path, None)?;
+        Ok(())
+    }
+}
+
+
+#[derive(Serialize)]
+struct ContactMethodEnvelopeRef<'a> {
+    contact_method: &'a ContactMethod,
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::fs::File;
+    use std::io::Read;
+
+    fn read_fixture() -> String {
+        let mut file = File::open("testdata/types/contact_methods.json").unwrap();
+        let mut data = String::new();
+        file.read_to_string(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn test_contact_methods_envelope_serde() {
+        let contact_methods_json = read_fixture();
+        let data = format!(r#"{{"contact_methods": {}}}"#, contact_methods_json);
+
+        let envelope: ContactMethodsEnvelope = serde_json::from_str(&data).unwrap();
+
+        let expected: serde_json::Value = serde_json::from_str(&data).unwrap();
+        let serialized: serde_json::Value = serde_json::from_str(
+            serde_json::to_string(&envelope).unwrap().as_ref()
+        ).unwrap();
+        assert_eq!(serialized, expected);
+    }
+
+    #[test]
+    fn test_contact_method_envelope_serde() {
+        let contact_methods: ContactMethods = serde_json::from_str(&read_fixture()).unwrap();
+        let contact_method = contact_methods.into_iter().next().unwrap();
+        let data = serde_json::to_string(&ContactMethodEnvelopeRef { contact_method: &contact_method }).unwrap();
+
+        let envelope: ContactMethodEnvelope = serde_json::from_str(&data).unwrap();
+
+        let expected: serde_json::Value = serde_json::from_str(&data).unwrap();
+        let serialized: serde_json::Value = serde_json::from_str(
+            serde_json::to_string(&envelope).unwrap().as_ref()
+        ).unwrap();
+        assert_eq!(serialized, expected);
+    }
+
+    #[test]
+    fn test_with_base_url_overrides_production_url() {
+        let client = Client::with_base_url("token", "http://127.0.0.1:0");
+        assert_eq!(client.base_url, "http://127.0.0.1:0");
+    }
+}
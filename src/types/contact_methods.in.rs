@@ -1,31 +1,24 @@
-use serde::de::{Deserialize, Deserializer};
+use chrono::{DateTime, FixedOffset};
+use serde::de::{Deserialize, Deserializer, Error};
 use serde::ser::{Serialize, Serializer};
+use serde_json;
 
-use super::reference::Reference;
+
+/// The kind of device a push notification contact method delivers to.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DeviceType {
+    Ios,
+    Android,
+}
 
 
+/// The sound played for a push notification.
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
-struct ContactMethodUnion {
-    // All Reference's
-    id: String,
-    summary: String,
-    #[serde(rename="type")]
-    type_: String,
-    #[serde(rename="self")]
-    self_: String,
-    html_url: Option<String>,
-
-    // All Concrete type fields
-    address: Option<String>,
-    label: Option<String>,
-    send_short_email: Option<bool>,
-    send_html_email: Option<bool>,
-    blacklisted: Option<bool>,
-    country_code: Option<u32>,
-    enabled: Option<bool>,
-    created_at: Option<String>,
-    device_type: Option<String>,
-    sounds: Option<Vec<PushContactMethodSound>>,
+#[serde(rename_all = "snake_case")]
+pub enum SoundType {
+    AlertHighUrgency,
+    AlertLowUrgency,
 }
 
 
@@ -35,268 +28,206 @@ pub struct PushContactMethodSound {
     pub file: String,
 
     /// The type of sound. Expected values include:
-    /// `alert_high_urgency` and `alert_high_urgency`.
+    /// `alert_high_urgency` and `alert_low_urgency`.
     #[serde(rename = "type")]
-    pub type_: String,
+    pub type_: SoundType,
 }
 
 
-#[derive(Debug, PartialEq)]
-pub enum ContactMethod {
-    ContactMethodReference {
-        reference: Reference,
-    },
-
-    EmailContactMethod{
-        reference: Reference,
-
-        /// The `address` to deliver to: email, phone number, etc.,
-        ///  depending on the type.
-        address: String,
-
-        /// The label (e.g., "Work", "Mobile", etc.).
-        label: String,
-
-        /// Send an abbreviated email message instead of the standard email
-        /// output. Useful for email-to-SMS gateways and email based pagers.
-        send_short_email: bool,
-
-        /// Send HTML e-mails.
-        send_html_email: bool,
-    },
-
-    PhoneContactMethod{
-        reference: Reference,
-
-        /// The `address` to deliver to: email, phone number, etc.,
-        ///  depending on the type.
-        address: String,
-
-        /// The label (e.g., "Work", "Mobile", etc.).
-        label: String,
-
-        /// If true, this phone has been blacklisted by
-        /// PagerDuty and no messages will be sent to it.
-        blacklisted: bool,
-
-        /// The 1-to-3 digit country calling code.
-        country_code: u32,
-    },
-
-    SmsContactMethod{
-        reference: Reference,
-
-        /// The `address` to deliver to: email, phone number, etc.,
-        ///  depending on the type.
-        address: String,
+/// A bare reference to a contact method, as embedded in e.g. a
+/// notification rule. PagerDuty emits several distinct `type` strings for
+/// this shape (`contact_method_reference`, `email_contact_method_reference`,
+/// etc.); `type_` retains whichever one was actually seen on the wire so
+/// that re-serializing a parsed reference round-trips byte-for-byte.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct ContactMethodReference {
+    pub id: String,
+    pub summary: String,
+    #[serde(rename = "type")]
+    pub type_: String,
+    #[serde(rename = "self")]
+    pub self_: String,
+    pub html_url: Option<String>,
+}
 
-        /// The label (e.g., "Work", "Mobile", etc.).
-        label: String,
 
-        /// If true, this phone has been blacklisted by
-        /// PagerDuty and no messages will be sent to it.
-        blacklisted: bool,
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct EmailContactMethod {
+    pub id: String,
+    pub summary: String,
+    #[serde(rename = "type")]
+    pub type_: String,
+    #[serde(rename = "self")]
+    pub self_: String,
+    pub html_url: Option<String>,
 
-        /// The 1-to-3 digit country calling code.
-        country_code: u32,
+    /// The `address` to deliver to: email, phone number, etc.,
+    ///  depending on the type.
+    pub address: String,
 
-        /// If true, this phone is capable of receiving SMS messages.
-        enabled: bool,
-    },
+    /// The label (e.g., "Work", "Mobile", etc.).
+    pub label: String,
 
-    PushNotificationContactMethod{
-        reference: Reference,
+    /// Send an abbreviated email message instead of the standard email
+    /// output. Useful for email-to-SMS gateways and email based pagers.
+    pub send_short_email: bool,
 
-        /// The `address` to deliver to: email, phone number, etc.,
-        ///  depending on the type.
-        address: String,
+    /// Send HTML e-mails.
+    pub send_html_email: bool,
+}
 
-        /// The label (e.g., "Work", "Mobile", etc.).
-        label: String,
 
-        /// If true, this phone has been blacklisted by PagerDuty and no messages will be sent to it.",
-        blacklisted: bool,
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct PhoneContactMethod {
+    pub id: String,
+    pub summary: String,
+    #[serde(rename = "type")]
+    pub type_: String,
+    #[serde(rename = "self")]
+    pub self_: String,
+    pub html_url: Option<String>,
 
-        // TODO(gary): Use date-time field?
-        /// Time at which the contact method was created.
-        created_at: String,
+    /// The `address` to deliver to: email, phone number, etc.,
+    ///  depending on the type.
+    pub address: String,
 
-        /// The type of device. Expected values include:
-        /// `ios` and `android`.
-        device_type: String,
+    /// The label (e.g., "Work", "Mobile", etc.).
+    pub label: String,
 
-        sounds: Vec<PushContactMethodSound>,
-    },
+    /// If true, this phone has been blacklisted by
+    /// PagerDuty and no messages will be sent to it.
+    pub blacklisted: bool,
 
+    /// The 1-to-3 digit country calling code.
+    pub country_code: u32,
 }
 
 
-impl Serialize for ContactMethod {
-    fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error>
-        where S: Serializer
-    {
-        let mut state = serializer.serialize_map(None)?;
-
-        match *self {
-            ContactMethod::ContactMethodReference{
-                ref reference
-            } => {
-                reference.serialize_key_vals(serializer, &mut state)?;
-            },
-            ContactMethod::EmailContactMethod{
-                ref reference, ref address, ref label,
-                ref send_short_email, ref send_html_email,
-            } => {
-                reference.serialize_key_vals(serializer, &mut state)?;
-
-                serializer.serialize_map_key(&mut state, "address")?;
-                serializer.serialize_map_value(&mut state, address)?;
-
-                serializer.serialize_map_key(&mut state, "label")?;
-                serializer.serialize_map_value(&mut state, label)?;
-
-                serializer.serialize_map_key(&mut state, "send_short_email")?;
-                serializer.serialize_map_value(&mut state, send_short_email)?;
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct SmsContactMethod {
+    pub id: String,
+    pub summary: String,
+    #[serde(rename = "type")]
+    pub type_: String,
+    #[serde(rename = "self")]
+    pub self_: String,
+    pub html_url: Option<String>,
 
-                serializer.serialize_map_key(&mut state, "send_html_email")?;
-                serializer.serialize_map_value(&mut state, send_html_email)?;
-            },
-            ContactMethod::PhoneContactMethod{
-                ref reference, ref address, ref label,
-                ref blacklisted, ref country_code,
-            } => {
-                reference.serialize_key_vals(serializer, &mut state)?;
+    /// The `address` to deliver to: email, phone number, etc.,
+    ///  depending on the type.
+    pub address: String,
 
-                serializer.serialize_map_key(&mut state, "address")?;
-                serializer.serialize_map_value(&mut state, address)?;
+    /// The label (e.g., "Work", "Mobile", etc.).
+    pub label: String,
 
-                serializer.serialize_map_key(&mut state, "label")?;
-                serializer.serialize_map_value(&mut state, label)?;
+    /// If true, this phone has been blacklisted by
+    /// PagerDuty and no messages will be sent to it.
+    pub blacklisted: bool,
 
-                serializer.serialize_map_key(&mut state, "country_code")?;
-                serializer.serialize_map_value(&mut state, country_code)?;
+    /// The 1-to-3 digit country calling code.
+    pub country_code: u32,
 
-                serializer.serialize_map_key(&mut state, "blacklisted")?;
-                serializer.serialize_map_value(&mut state, blacklisted)?;
+    /// If true, this phone is capable of receiving SMS messages.
+    pub enabled: bool,
+}
 
-            },
-            ContactMethod::SmsContactMethod{
-                ref reference, ref address, ref label,
-                ref blacklisted, ref country_code, ref enabled,
-            } => {
-                reference.serialize_key_vals(serializer, &mut state)?;
 
-                serializer.serialize_map_key(&mut state, "address")?;
-                serializer.serialize_map_value(&mut state, address)?;
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct PushNotificationContactMethod {
+    pub id: String,
+    pub summary: String,
+    #[serde(rename = "type")]
+    pub type_: String,
+    #[serde(rename = "self")]
+    pub self_: String,
+    pub html_url: Option<String>,
 
-                serializer.serialize_map_key(&mut state, "label")?;
-                serializer.serialize_map_value(&mut state, label)?;
+    /// The `address` to deliver to: email, phone number, etc.,
+    ///  depending on the type.
+    pub address: String,
 
-                serializer.serialize_map_key(&mut state, "country_code")?;
-                serializer.serialize_map_value(&mut state, country_code)?;
+    /// The label (e.g., "Work", "Mobile", etc.).
+    pub label: String,
 
-                serializer.serialize_map_key(&mut state, "blacklisted")?;
-                serializer.serialize_map_value(&mut state, blacklisted)?;
+    /// If true, this phone has been blacklisted by PagerDuty and no messages will be sent to it.",
+    pub blacklisted: bool,
 
-                serializer.serialize_map_key(&mut state, "enabled")?;
-                serializer.serialize_map_value(&mut state, enabled)?;
-            },
-            ContactMethod::PushNotificationContactMethod{
-                ref reference, ref address, ref label,
-                ref blacklisted, ref created_at, ref device_type,
-                ref sounds,
-            } => {
-                reference.serialize_key_vals(serializer, &mut state)?;
+    /// Time at which the contact method was created.
+    pub created_at: DateTime<FixedOffset>,
 
-                serializer.serialize_map_key(&mut state, "address")?;
-                serializer.serialize_map_value(&mut state, address)?;
+    /// The type of device.
+    pub device_type: DeviceType,
 
-                serializer.serialize_map_key(&mut state, "label")?;
-                serializer.serialize_map_value(&mut state, label)?;
+    pub sounds: Vec<PushContactMethodSound>,
+}
 
-                serializer.serialize_map_key(&mut state, "device_type")?;
-                serializer.serialize_map_value(&mut state, device_type)?;
 
-                serializer.serialize_map_key(&mut state, "sounds")?;
-                serializer.serialize_map_value(&mut state, sounds)?;
+#[derive(Debug, PartialEq)]
+pub enum ContactMethod {
+    ContactMethodReference(ContactMethodReference),
+    EmailContactMethod(EmailContactMethod),
+    PhoneContactMethod(PhoneContactMethod),
+    SmsContactMethod(SmsContactMethod),
+    PushNotificationContactMethod(PushNotificationContactMethod),
+}
 
-                serializer.serialize_map_key(&mut state, "blacklisted")?;
-                serializer.serialize_map_value(&mut state, blacklisted)?;
 
-                serializer.serialize_map_key(&mut state, "created_at")?;
-                serializer.serialize_map_value(&mut state, created_at)?;
-            },
+impl Serialize for ContactMethod {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        match *self {
+            ContactMethod::ContactMethodReference(ref v) => v.serialize(serializer),
+            ContactMethod::EmailContactMethod(ref v) => v.serialize(serializer),
+            ContactMethod::PhoneContactMethod(ref v) => v.serialize(serializer),
+            ContactMethod::SmsContactMethod(ref v) => v.serialize(serializer),
+            ContactMethod::PushNotificationContactMethod(ref v) => v.serialize(serializer),
         }
-
-        serializer.serialize_map_end(state)
     }
 }
 
-impl Deserialize for ContactMethod {
-    fn deserialize<D>(deserializer: &mut D) -> Result<ContactMethod, D::Error>
-        where D: Deserializer
+impl<'de> Deserialize<'de> for ContactMethod {
+    fn deserialize<D>(deserializer: D) -> Result<ContactMethod, D::Error>
+        where D: Deserializer<'de>
     {
-        let union = ContactMethodUnion::deserialize(deserializer)?;
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let type_ = value.get("type")
+            .and_then(|t| t.as_str())
+            .map(|s| s.to_owned())
+            .ok_or_else(|| D::Error::missing_field("type"))?;
 
-        let reference = Reference {
-            id: union.id,
-            summary: union.summary,
-            type_: union.type_,
-            self_: union.self_,
-            html_url: union.html_url,
-        };
-
-        match reference.type_.as_ref() {
+        match type_.as_ref() {
             "contact_method_reference" |
             "email_contact_method_reference" |
             "phone_contact_method_reference" |
             "sms_contact_method_reference" |
             "push_notification_contact_method_reference"
             => {
-                Ok(ContactMethod::ContactMethodReference {
-                    reference: reference,
-                })
+                serde_json::from_value(value)
+                    .map(ContactMethod::ContactMethodReference)
+                    .map_err(D::Error::custom)
             },
             "email_contact_method" => {
-                Ok(ContactMethod::EmailContactMethod {
-                    reference: reference,
-                    address: union.address.expect("address"),
-                    label: union.label.expect("label"),
-                    send_short_email: union.send_short_email.expect("send_short_email"),
-                    send_html_email: union.send_html_email.expect("send_html_email"),
-                })
+                serde_json::from_value(value)
+                    .map(ContactMethod::EmailContactMethod)
+                    .map_err(D::Error::custom)
             },
             "phone_contact_method" => {
-                Ok(ContactMethod::PhoneContactMethod {
-                    reference: reference,
-                    address: union.address.expect("address"),
-                    label: union.label.expect("label"),
-                    blacklisted: union.blacklisted.expect("blacklisted"),
-                    country_code: union.country_code.expect("country_code"),
-                })
+                serde_json::from_value(value)
+                    .map(ContactMethod::PhoneContactMethod)
+                    .map_err(D::Error::custom)
             },
             "sms_contact_method" => {
-                Ok(ContactMethod::SmsContactMethod {
-                    reference: reference,
-                    address: union.address.expect("address"),
-                    label: union.label.expect("label"),
-                    blacklisted: union.blacklisted.expect("blacklisted"),
-                    country_code: union.country_code.expect("country_code"),
-                    enabled: union.enabled.expect("enabled"),
-                })
+                serde_json::from_value(value)
+                    .map(ContactMethod::SmsContactMethod)
+                    .map_err(D::Error::custom)
             },
             "push_notification_contact_method" => {
-                Ok(ContactMethod::PushNotificationContactMethod {
-                    reference: reference,
-                    address: union.address.expect("address"),
-                    label: union.label.expect("label"),
-                    blacklisted: union.blacklisted.expect("blacklisted"),
-                    created_at: union.created_at.expect("created_at"),
-                    device_type: union.device_type.expect("device_type"),
-                    sounds: union.sounds.expect("sounds"),
-                })
+                serde_json::from_value(value)
+                    .map(ContactMethod::PushNotificationContactMethod)
+                    .map_err(D::Error::custom)
             },
-            _ => panic!("fuuuuuuu"),
+            other => Err(D::Error::custom(format!("unknown contact method type: {}", other))),
         }
     }
 }
@@ -312,7 +243,6 @@ mod tests {
     use serde_json;
     use std::fs::File;
     use std::io::Read;
-    use super::super::reference::Reference;
 
     #[test]
     fn test_serde() {
@@ -326,75 +256,65 @@ mod tests {
             contact_methods,
             vec![
 
-                ContactMethod::ContactMethodReference {
-                    reference: Reference {
-                        id: "PPPIOPG".into(),
-                        summary: "Default".into(),
-                        type_: "email_contact_method_reference".into(),
-                        self_: "https://api.pagerduty.com/users/PZ7JFQ7/contact_methods/PPPIOPG".into(),
-                        html_url: None,
-                    },
-                },
-                ContactMethod::EmailContactMethod {
-                    reference: Reference {
-                        id: "P33R0ZA".into(),
-                        summary: "Work".into(),
-                        type_: "email_contact_method".into(),
-                        self_: "https://api.pagerduty.com/users/PZ7JFQ7/contact_methods/P33R0ZA".into(),
-                        html_url: None,
-                    },
+                ContactMethod::ContactMethodReference(ContactMethodReference {
+                    id: "PPPIOPG".into(),
+                    summary: "Default".into(),
+                    type_: "email_contact_method_reference".into(),
+                    self_: "https://api.pagerduty.com/users/PZ7JFQ7/contact_methods/PPPIOPG".into(),
+                    html_url: None,
+                }),
+                ContactMethod::EmailContactMethod(EmailContactMethod {
+                    id: "P33R0ZA".into(),
+                    summary: "Work".into(),
+                    type_: "email_contact_method".into(),
+                    self_: "https://api.pagerduty.com/users/PZ7JFQ7/contact_methods/P33R0ZA".into(),
+                    html_url: None,
                     address: "alejandro@example.com".into(),
                     label: "Work".into(),
                     send_short_email: false,
                     send_html_email: false,
-                },
-                ContactMethod::SmsContactMethod {
-                    reference: Reference {
-                        id: "PEC83HY".into(),
-                        summary: "Mobile".into(),
-                        type_: "sms_contact_method".into(),
-                        self_: "https://api.pagerduty.com/users/PGJ36Z3/contact_methods/PEC83HY".into(),
-                        html_url: None,
-                    },
+                }),
+                ContactMethod::SmsContactMethod(SmsContactMethod {
+                    id: "PEC83HY".into(),
+                    summary: "Mobile".into(),
+                    type_: "sms_contact_method".into(),
+                    self_: "https://api.pagerduty.com/users/PGJ36Z3/contact_methods/PEC83HY".into(),
+                    html_url: None,
                     address: "4155809923".into(),
                     label: "Mobile".into(),
                     blacklisted: false,
                     country_code: 1,
                     enabled: true,
-                },
-                ContactMethod::PhoneContactMethod {
-                    reference: Reference {
-                        id: "PBUSVMD".into(),
-                        summary: "Mobile".into(),
-                        type_: "phone_contact_method".into(),
-                        self_: "https://api.pagerduty.com/users/P1RQ0Z6/contact_methods/PBUSVMD".into(),
-                        html_url: None,
-                    },
+                }),
+                ContactMethod::PhoneContactMethod(PhoneContactMethod {
+                    id: "PBUSVMD".into(),
+                    summary: "Mobile".into(),
+                    type_: "phone_contact_method".into(),
+                    self_: "https://api.pagerduty.com/users/P1RQ0Z6/contact_methods/PBUSVMD".into(),
+                    html_url: None,
                     address: "7076949626".into(),
                     label: "Mobile".into(),
                     blacklisted: false,
                     country_code: 1,
-                },
-                ContactMethod::PushNotificationContactMethod {
-                    reference: Reference {
-                        id: "P4G3JKD".into(),
-                        summary: "Alex\'s iPhone".into(),
-                        type_: "push_notification_contact_method".into(),
-                        self_: "https://api.pagerduty.com/users/P1RQ0Z6/contact_methods/P4G3JKD".into(),
-                        html_url: None,
-                    },
+                }),
+                ContactMethod::PushNotificationContactMethod(PushNotificationContactMethod {
+                    id: "P4G3JKD".into(),
+                    summary: "Alex\'s iPhone".into(),
+                    type_: "push_notification_contact_method".into(),
+                    self_: "https://api.pagerduty.com/users/P1RQ0Z6/contact_methods/P4G3JKD".into(),
+                    html_url: None,
                     address: "fcbaba06abe7533794b0dd7c3f4427b574772c01445e06bb5a006c33f14d95d0".into(),
                     label: "Alex\'s iPhone".into(),
                     blacklisted: false,
-                    created_at: "2016-07-11T11:36:41-07:00".into(),
-                    device_type: "ios".into(),
+                    created_at: DateTime::parse_from_rfc3339("2016-07-11T11:36:41-07:00").unwrap(),
+                    device_type: DeviceType::Ios,
                     sounds: vec![
                         PushContactMethodSound {
                             file: "default".into(),
-                            type_: "alert_high_urgency".into(),
+                            type_: SoundType::AlertHighUrgency,
                         }
                     ],
-                }
+                })
             ]
         );
 
@@ -405,4 +325,89 @@ mod tests {
         ).unwrap();
         assert_eq!(serialized, expected)
     }
+
+    #[test]
+    fn test_deserialize_unknown_type_returns_err() {
+        let data = r#"{
+            "id": "P33R0ZA",
+            "summary": "Work",
+            "type": "carrier_pigeon_contact_method",
+            "self": "https://api.pagerduty.com/users/PZ7JFQ7/contact_methods/P33R0ZA",
+            "html_url": null
+        }"#;
+
+        let result: Result<ContactMethod, _> = serde_json::from_str(data);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_deserialize_email_missing_address_returns_err() {
+        let data = r#"{
+            "id": "P33R0ZA",
+            "summary": "Work",
+            "type": "email_contact_method",
+            "self": "https://api.pagerduty.com/users/PZ7JFQ7/contact_methods/P33R0ZA",
+            "html_url": null,
+            "label": "Work",
+            "send_short_email": false,
+            "send_html_email": false
+        }"#;
+
+        let result: Result<ContactMethod, _> = serde_json::from_str(data);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_deserialize_phone_missing_blacklisted_returns_err() {
+        let data = r#"{
+            "id": "PBUSVMD",
+            "summary": "Mobile",
+            "type": "phone_contact_method",
+            "self": "https://api.pagerduty.com/users/P1RQ0Z6/contact_methods/PBUSVMD",
+            "html_url": null,
+            "address": "7076949626",
+            "label": "Mobile",
+            "country_code": 1
+        }"#;
+
+        let result: Result<ContactMethod, _> = serde_json::from_str(data);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_deserialize_sms_missing_enabled_returns_err() {
+        let data = r#"{
+            "id": "PEC83HY",
+            "summary": "Mobile",
+            "type": "sms_contact_method",
+            "self": "https://api.pagerduty.com/users/PGJ36Z3/contact_methods/PEC83HY",
+            "html_url": null,
+            "address": "4155809923",
+            "label": "Mobile",
+            "blacklisted": false,
+            "country_code": 1
+        }"#;
+
+        let result: Result<ContactMethod, _> = serde_json::from_str(data);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_deserialize_push_missing_sounds_returns_err() {
+        let data = r#"{
+            "id": "P4G3JKD",
+            "summary": "Alex's iPhone",
+            "type": "push_notification_contact_method",
+            "self": "https://api.pagerduty.com/users/P1RQ0Z6/contact_methods/P4G3JKD",
+            "html_url": null,
+            "address": "fcbaba06abe7533794b0dd7c3f4427b574772c01445e06bb5a006c33f14d95d0",
+            "label": "Alex's iPhone",
+            "blacklisted": false,
+            "created_at": "2016-07-11T11:36:41-07:00",
+            "device_type": "ios"
+        }"#;
+
+        let result: Result<ContactMethod, _> = serde_json::from_str(data);
+        assert!(result.is_err());
+    }
 }
@@ -0,0 +1,76 @@
+use super::contact_methods::ContactMethod;
+
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct NotificationRule {
+    pub id: String,
+    pub summary: String,
+    #[serde(rename = "type")]
+    pub type_: String,
+    #[serde(rename = "self")]
+    pub self_: String,
+    pub html_url: Option<String>,
+
+    /// How long to wait, after the prior rule in the escalation chain has
+    /// had its chance to notify, before this rule fires.
+    pub start_delay_in_minutes: u32,
+
+    /// The urgency level this rule applies to. Expected values include:
+    /// `high` and `low`.
+    pub urgency: String,
+
+    /// The contact method this rule notifies.
+    pub contact_method: ContactMethod,
+}
+
+
+pub type NotificationRules = Vec<NotificationRule>;
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use serde_json;
+    use std::fs::File;
+    use std::io::Read;
+    use super::super::contact_methods::ContactMethodReference;
+
+    #[test]
+    fn test_serde() {
+        let mut file = File::open("testdata/types/notification_rules.json").unwrap();
+        let mut data = String::new();
+        file.read_to_string(&mut data).unwrap();
+        let notification_rules: NotificationRules = serde_json::from_str(&data).unwrap();
+
+        // Verify deserialization.
+        assert_eq!(
+            notification_rules,
+            vec![
+                NotificationRule {
+                    id: "P8GCNR6".into(),
+                    summary: "Delay 0".into(),
+                    type_: "assignment_notification_rule".into(),
+                    self_: "https://api.pagerduty.com/users/PZ7JFQ7/notification_rules/P8GCNR6".into(),
+                    html_url: None,
+                    start_delay_in_minutes: 0,
+                    urgency: "high".into(),
+                    contact_method: ContactMethod::ContactMethodReference(ContactMethodReference {
+                        id: "PPPIOPG".into(),
+                        summary: "Default".into(),
+                        type_: "contact_method_reference".into(),
+                        self_: "https://api.pagerduty.com/users/PZ7JFQ7/contact_methods/PPPIOPG".into(),
+                        html_url: None,
+                    }),
+                },
+            ]
+        );
+
+        // Verify that serialization round-trips.
+        let expected: serde_json::Value = serde_json::from_str(&data).unwrap();
+        let serialized: serde_json::Value = serde_json::from_str(
+            serde_json::to_string(&notification_rules).unwrap().as_ref()
+        ).unwrap();
+        assert_eq!(serialized, expected)
+    }
+}